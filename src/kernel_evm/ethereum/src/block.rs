@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use primitive_types::{H256, U256};
+use rlp::RlpStream;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::transaction::TransactionHash;
+
+/// An L2 block, as tracked by the rollup.
+pub struct L2Block {
+    pub number: U256,
+    pub hash: H256,
+    pub transactions: Vec<TransactionHash>,
+}
+
+impl L2Block {
+    /// Builds a block from its number and the hashes of the transactions it
+    /// contains, deriving its hash from those fields.
+    pub fn new(number: U256, transactions: Vec<TransactionHash>) -> Self {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&number);
+        stream.begin_list(transactions.len());
+        for tx_hash in &transactions {
+            stream.append(&tx_hash.to_vec());
+        }
+
+        let mut hasher = Keccak::v256();
+        hasher.update(&stream.out());
+        let mut hash = [0u8; 32];
+        hasher.finalize(&mut hash);
+
+        L2Block {
+            number,
+            hash: H256::from(hash),
+            transactions,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+pub mod block;
+pub mod log;
+pub mod transaction;
+pub mod wei;
@@ -0,0 +1,294 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use primitive_types::{H160, H256, U256};
+use rlp::{DecoderError, Rlp, RlpStream};
+
+use crate::log::{self, LogEntry, LOGS_BLOOM_SIZE};
+
+pub const TRANSACTION_HASH_SIZE: usize = 32;
+pub type TransactionHash = [u8; TRANSACTION_HASH_SIZE];
+
+/// The EIP-2718 envelope type of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    Legacy = 0,
+    Eip2930 = 1,
+    Eip1559 = 2,
+}
+
+/// The outcome of executing a transaction, as reported in its receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionStatus {
+    Failure = 0,
+    Success = 1,
+}
+
+#[derive(Debug)]
+pub enum TransactionError {
+    InvalidRlp(DecoderError),
+}
+
+impl From<DecoderError> for TransactionError {
+    fn from(error: DecoderError) -> Self {
+        TransactionError::InvalidRlp(error)
+    }
+}
+
+/// A decoded Ethereum transaction, as returned by `eth_getTransactionByHash`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionObject {
+    pub from: H160,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub gas_price: U256,
+    pub hash: TransactionHash,
+    pub input: Vec<u8>,
+    pub nonce: U256,
+    pub to: Option<H160>,
+    pub index: u32,
+    pub value: U256,
+    pub v: U256,
+    pub r: H256,
+    pub s: H256,
+    pub type_: u8,
+    pub access_list: Vec<(H160, Vec<H256>)>,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    /// The EIP-155 chain id, part of the signed payload of EIP-2930 and
+    /// EIP-1559 transactions (absent for legacy ones, whose replay
+    /// protection is instead folded into `v`).
+    pub chain_id: U256,
+}
+
+fn append_to(stream: &mut RlpStream, to: Option<H160>) {
+    match to {
+        Some(to) => stream.append(&to),
+        None => stream.append_empty_data(),
+    };
+}
+
+fn append_access_list(stream: &mut RlpStream, access_list: &[(H160, Vec<H256>)]) {
+    stream.begin_list(access_list.len());
+    for (address, storage_keys) in access_list {
+        stream.begin_list(2);
+        stream.append(address);
+        stream.begin_list(storage_keys.len());
+        for key in storage_keys {
+            stream.append(key);
+        }
+    }
+}
+
+impl TransactionObject {
+    /// RLP encoding of the signed transaction itself, i.e. exactly the
+    /// fields a verifier would recompute from the raw transaction bytes --
+    /// unlike [Self::rlp_bytes], it carries none of the RPC-only fields
+    /// (`gas_used`, `index`, ...). Used as the leaf value of the block's
+    /// `transactions_root`, mirroring the EIP-2718 type prefixing already
+    /// applied to receipts.
+    pub fn signed_rlp_bytes(&self) -> Vec<u8> {
+        if self.type_ == TransactionType::Eip1559 as u8 {
+            let mut stream = RlpStream::new_list(12);
+            stream.append(&self.chain_id);
+            stream.append(&self.nonce);
+            stream.append(&self.max_priority_fee_per_gas);
+            stream.append(&self.max_fee_per_gas);
+            stream.append(&self.gas);
+            append_to(&mut stream, self.to);
+            stream.append(&self.value);
+            stream.append(&self.input);
+            append_access_list(&mut stream, &self.access_list);
+            stream.append(&self.v);
+            stream.append(&self.r);
+            stream.append(&self.s);
+            return prefix_with_type(self.type_, stream.out().to_vec());
+        }
+        if self.type_ == TransactionType::Eip2930 as u8 {
+            let mut stream = RlpStream::new_list(11);
+            stream.append(&self.chain_id);
+            stream.append(&self.nonce);
+            stream.append(&self.gas_price);
+            stream.append(&self.gas);
+            append_to(&mut stream, self.to);
+            stream.append(&self.value);
+            stream.append(&self.input);
+            append_access_list(&mut stream, &self.access_list);
+            stream.append(&self.v);
+            stream.append(&self.r);
+            stream.append(&self.s);
+            return prefix_with_type(self.type_, stream.out().to_vec());
+        }
+
+        let mut stream = RlpStream::new_list(9);
+        stream.append(&self.nonce);
+        stream.append(&self.gas_price);
+        stream.append(&self.gas);
+        append_to(&mut stream, self.to);
+        stream.append(&self.value);
+        stream.append(&self.input);
+        stream.append(&self.v);
+        stream.append(&self.r);
+        stream.append(&self.s);
+        stream.out().to_vec()
+    }
+}
+
+fn prefix_with_type(type_: u8, payload: Vec<u8>) -> Vec<u8> {
+    if type_ == TransactionType::Legacy as u8 {
+        payload
+    } else {
+        let mut bytes = Vec::with_capacity(payload.len() + 1);
+        bytes.push(type_);
+        bytes.extend_from_slice(&payload);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_signed_rlp_bytes_has_no_type_prefix() {
+        let object = TransactionObject {
+            from: H160::zero(),
+            gas: U256::from(21_000),
+            gas_used: U256::from(21_000),
+            gas_price: U256::from(1),
+            hash: [0u8; TRANSACTION_HASH_SIZE],
+            input: vec![],
+            nonce: U256::zero(),
+            to: Some(H160::zero()),
+            index: 0,
+            value: U256::zero(),
+            v: U256::from(27),
+            r: H256::zero(),
+            s: H256::zero(),
+            type_: TransactionType::Legacy as u8,
+            access_list: vec![],
+            max_fee_per_gas: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            chain_id: U256::one(),
+        };
+        let encoded = object.signed_rlp_bytes();
+        // A legacy transaction's RLP encoding is a list, so it starts with a
+        // byte in `0xc0..=0xff` -- never an EIP-2718 type prefix (`<= 0x7f`).
+        assert!(encoded[0] > 0x7f);
+    }
+
+    #[test]
+    fn eip1559_signed_rlp_bytes_starts_with_its_type_prefix() {
+        let object = TransactionObject {
+            from: H160::zero(),
+            gas: U256::from(21_000),
+            gas_used: U256::from(21_000),
+            gas_price: U256::zero(),
+            hash: [0u8; TRANSACTION_HASH_SIZE],
+            input: vec![],
+            nonce: U256::zero(),
+            to: Some(H160::zero()),
+            index: 0,
+            value: U256::zero(),
+            v: U256::from(1),
+            r: H256::zero(),
+            s: H256::zero(),
+            type_: TransactionType::Eip1559 as u8,
+            access_list: vec![],
+            max_fee_per_gas: U256::from(10),
+            max_priority_fee_per_gas: U256::from(1),
+            chain_id: U256::one(),
+        };
+        let encoded = object.signed_rlp_bytes();
+        assert_eq!(encoded[0], TransactionType::Eip1559 as u8);
+    }
+}
+
+/// The receipt of an executed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    pub hash: TransactionHash,
+    pub index: u32,
+    pub status: TransactionStatus,
+    pub cumulative_gas_used: U256,
+    pub logs: Vec<LogEntry>,
+    pub type_: u8,
+}
+
+impl TransactionReceipt {
+    /// This receipt's `logs_bloom`, derived from its logs rather than
+    /// stored, so it can never drift out of sync with them.
+    pub fn logs_bloom(&self) -> [u8; LOGS_BLOOM_SIZE] {
+        log::logs_bloom(&self.logs)
+    }
+
+    pub fn rlp_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(5);
+        stream.append(&self.hash.to_vec());
+        stream.append(&(self.index as u64));
+        stream.append(&(self.status as u8));
+        stream.append(&self.cumulative_gas_used);
+        stream.begin_list(self.logs.len());
+        for log in &self.logs {
+            stream.append(log);
+        }
+        stream.out().to_vec()
+    }
+
+    /// RLP encoding of the canonical Ethereum receipt itself --
+    /// `(status, cumulativeGasUsed, logsBloom, logs)`, EIP-2718-prefixed for
+    /// typed transactions -- unlike [Self::rlp_bytes], it carries none of
+    /// the RPC-only fields (`hash`, `index`). Used as the leaf value of the
+    /// block's `receipts_root`, mirroring [TransactionObject::signed_rlp_bytes].
+    pub fn consensus_rlp_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&(self.status as u8));
+        stream.append(&self.cumulative_gas_used);
+        stream.append(&self.logs_bloom().to_vec());
+        stream.begin_list(self.logs.len());
+        for log in &self.logs {
+            stream.append(log);
+        }
+        prefix_with_type(self.type_, stream.out().to_vec())
+    }
+
+    pub fn from_rlp_bytes(bytes: &[u8]) -> Result<Self, TransactionError> {
+        let rlp = Rlp::new(bytes);
+        let hash: Vec<u8> = rlp.val_at(0)?;
+        let hash: TransactionHash = hash
+            .try_into()
+            .map_err(|_| TransactionError::InvalidRlp(DecoderError::RlpInvalidLength))?;
+        let index: u64 = rlp.val_at(1)?;
+        let status: u8 = rlp.val_at(2)?;
+        let status = if status == 0 {
+            TransactionStatus::Failure
+        } else {
+            TransactionStatus::Success
+        };
+        let cumulative_gas_used: U256 = rlp.val_at(3)?;
+        let logs_rlp = rlp.at(4)?;
+        let logs = logs_rlp
+            .iter()
+            .map(|log_rlp| {
+                let address: H160 = log_rlp.val_at(0)?;
+                let topics: Vec<H256> = log_rlp.list_at(1)?;
+                let data: Vec<u8> = log_rlp.val_at(2)?;
+                Ok(LogEntry {
+                    address,
+                    topics,
+                    data,
+                })
+            })
+            .collect::<Result<Vec<LogEntry>, DecoderError>>()?;
+
+        Ok(TransactionReceipt {
+            hash,
+            index: index as u32,
+            status,
+            cumulative_gas_used,
+            logs,
+            type_: TransactionType::Legacy as u8,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use primitive_types::U256;
+
+/// A quantity of Wei, the smallest denomination of Ether.
+///
+/// This is a thin helper around [U256] matching the little-endian encoding
+/// the kernel uses for 256 bit storage values.
+pub struct Wei;
+
+impl Wei {
+    pub fn from_little_endian(bytes: &[u8]) -> U256 {
+        U256::from_little_endian(bytes)
+    }
+}
@@ -0,0 +1,247 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use primitive_types::{H160, H256, U256};
+use tezos_smart_rollup_core::MAX_FILE_CHUNK_SIZE;
+use tezos_smart_rollup_host::path::*;
+use tezos_smart_rollup_host::runtime::Runtime;
+
+use crate::error::Error;
+
+const WORLD_STATE: RefPath = RefPath::assert_from(b"/evm/world_state");
+// Flat list of every address ever touched, 20 bytes each: the real durable
+// storage host has no way to enumerate subkeys by name (only to count
+// them), so this explicit index is the only way to recover the set of
+// accounts that exist.
+const ACCOUNTS_INDEX: RefPath = RefPath::assert_from(b"/evm/world_state/index");
+
+const ACCOUNT_NONCE: RefPath = RefPath::assert_from(b"/nonce");
+const ACCOUNT_BALANCE: RefPath = RefPath::assert_from(b"/balance");
+const ACCOUNT_CODE_HASH: RefPath = RefPath::assert_from(b"/code_hash");
+// Flat list of every storage key ever touched for a given account, 32 bytes
+// each, mirroring [ACCOUNTS_INDEX] at the per-account level.
+const ACCOUNT_STORAGE_INDEX: RefPath = RefPath::assert_from(b"/storage_index");
+const ACCOUNT_STORAGE: RefPath = RefPath::assert_from(b"/storage");
+
+const ADDRESS_SIZE: usize = 20;
+const WORD_SIZE: usize = 32;
+
+/// Upper bound on the number of accounts `eth_getProof` will read back in a
+/// single call, so that a query against a world state with a huge number of
+/// accounts fails loudly instead of paying an unbounded cost.
+///
+/// `eth_getProof` recomputes every account's `storage_root` to rebuild the
+/// account trie, so a call's worst-case storage-read cost is
+/// `MAX_PROOF_ACCOUNTS * MAX_PROOF_STORAGE_SLOTS`. This is kept low enough
+/// that the product stays within what the rollup can afford per call; it is
+/// a hard operational ceiling, not just an anti-abuse bound.
+pub const MAX_PROOF_ACCOUNTS: usize = 100;
+/// Upper bound on the number of storage slots `eth_getProof` will read back
+/// for a single account. See [MAX_PROOF_ACCOUNTS] for why this is kept low.
+pub const MAX_PROOF_STORAGE_SLOTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Account {
+    pub nonce: U256,
+    pub balance: U256,
+    pub code_hash: H256,
+}
+
+pub fn account_path(address: &H160) -> Result<OwnedPath, Error> {
+    let raw_path: Vec<u8> = format!("/{}", hex::encode(address.as_bytes())).into();
+    let path = OwnedPath::try_from(raw_path)?;
+    concat(&WORLD_STATE, &path).map_err(Error::from)
+}
+
+/// Reads an arbitrarily large value from storage, transparently chunking
+/// the reads at [MAX_FILE_CHUNK_SIZE] boundaries.
+pub fn store_read_all<Host: Runtime, T: Path>(
+    host: &mut Host,
+    path: &T,
+) -> Result<Vec<u8>, Error> {
+    let size = host.store_value_size(path)?;
+    let mut buffer = Vec::with_capacity(size);
+    let mut offset = 0;
+    while offset < size {
+        let chunk = host.store_read(path, offset, MAX_FILE_CHUNK_SIZE)?;
+        offset += chunk.len();
+        buffer.extend_from_slice(&chunk);
+    }
+    Ok(buffer)
+}
+
+/// Writes an arbitrarily large value to storage, transparently chunking the
+/// writes at [MAX_FILE_CHUNK_SIZE] boundaries.
+pub fn store_write_all<Host: Runtime, T: Path>(
+    host: &mut Host,
+    path: &T,
+    data: &[u8],
+) -> Result<(), Error> {
+    for (i, chunk) in data.chunks(MAX_FILE_CHUNK_SIZE).enumerate() {
+        host.store_write(path, chunk, i * MAX_FILE_CHUNK_SIZE)?;
+    }
+    if data.is_empty() {
+        host.store_write(path, &[], 0)?;
+    }
+    Ok(())
+}
+
+fn read_fixed_size_index<Host: Runtime, T: Path, V, const N: usize>(
+    host: &mut Host,
+    path: &T,
+    max_entries: usize,
+    too_many: Error,
+    from_bytes: impl Fn([u8; N]) -> V,
+) -> Result<Vec<V>, Error> {
+    // An index that was never touched (no account/storage key registered
+    // yet) simply has no entries, rather than being an error.
+    let bytes = match host.store_has(path)? {
+        Some(_) => store_read_all(host, path)?,
+        None => vec![],
+    };
+    if bytes.len() % N != 0 {
+        return Err(Error::TruncatedIndex);
+    }
+    if bytes.len() / N > max_entries {
+        return Err(too_many);
+    }
+    Ok(bytes
+        .chunks(N)
+        .map(|chunk| from_bytes(chunk.try_into().expect("chunk length checked above")))
+        .collect())
+}
+
+/// Appends `entry` to the flat index at `index_path` unless it's already
+/// present, initializing the index the first time it's touched. The
+/// write-side counterpart of [read_fixed_size_index].
+fn append_to_index<Host: Runtime, T: Path>(
+    host: &mut Host,
+    index_path: &T,
+    entry: &[u8],
+) -> Result<(), Error> {
+    let existing = match host.store_has(index_path)? {
+        Some(_) => store_read_all(host, index_path)?,
+        None => vec![],
+    };
+    if existing.chunks(entry.len()).any(|chunk| chunk == entry) {
+        return Ok(());
+    }
+    let mut updated = existing;
+    updated.extend_from_slice(entry);
+    store_write_all(host, index_path, &updated)
+}
+
+fn read_account<Host: Runtime>(
+    host: &mut Host,
+    account_path: &OwnedPath,
+) -> Result<Account, Error> {
+    let nonce_path = concat(account_path, &ACCOUNT_NONCE)?;
+    let balance_path = concat(account_path, &ACCOUNT_BALANCE)?;
+    let code_hash_path = concat(account_path, &ACCOUNT_CODE_HASH)?;
+
+    let nonce = U256::from_little_endian(&store_read_all(host, &nonce_path)?);
+    let balance = U256::from_little_endian(&store_read_all(host, &balance_path)?);
+    let code_hash = H256::from_slice(&store_read_all(host, &code_hash_path)?);
+
+    Ok(Account {
+        nonce,
+        balance,
+        code_hash,
+    })
+}
+
+/// Writes `account`'s full state, registering its address in
+/// [ACCOUNTS_INDEX] the first time it's touched so [list_accounts] can find
+/// it again. The write-side counterpart of [read_account]/[list_accounts];
+/// whatever applies transactions is expected to call this every time an
+/// account's nonce, balance or code changes.
+pub fn store_account<Host: Runtime>(
+    host: &mut Host,
+    address: &H160,
+    account: &Account,
+) -> Result<(), Error> {
+    append_to_index(host, &ACCOUNTS_INDEX, address.as_bytes())?;
+
+    let account_path = account_path(address)?;
+    let nonce_path = concat(&account_path, &ACCOUNT_NONCE)?;
+    let balance_path = concat(&account_path, &ACCOUNT_BALANCE)?;
+    let code_hash_path = concat(&account_path, &ACCOUNT_CODE_HASH)?;
+
+    let mut nonce_bytes = [0u8; WORD_SIZE];
+    account.nonce.to_little_endian(&mut nonce_bytes);
+    let mut balance_bytes = [0u8; WORD_SIZE];
+    account.balance.to_little_endian(&mut balance_bytes);
+
+    store_write_all(host, &nonce_path, &nonce_bytes)?;
+    store_write_all(host, &balance_path, &balance_bytes)?;
+    store_write_all(host, &code_hash_path, account.code_hash.as_bytes())
+}
+
+/// Writes a single storage slot for the account at `account_path`,
+/// registering its key in that account's [ACCOUNT_STORAGE_INDEX] the first
+/// time it's touched so [list_storage] can find it again. The write-side
+/// counterpart of [list_storage].
+pub fn store_storage<Host: Runtime>(
+    host: &mut Host,
+    account_path: &OwnedPath,
+    key: &H256,
+    value: U256,
+) -> Result<(), Error> {
+    let index_path = concat(account_path, &ACCOUNT_STORAGE_INDEX)?;
+    append_to_index(host, &index_path, key.as_bytes())?;
+
+    let storage_path = concat(account_path, &ACCOUNT_STORAGE)?;
+    let raw_key_path: Vec<u8> = format!("/{}", hex::encode(key.as_bytes())).into();
+    let key_path = concat(&storage_path, &OwnedPath::try_from(raw_key_path)?)?;
+
+    let mut value_bytes = [0u8; WORD_SIZE];
+    value.to_little_endian(&mut value_bytes);
+    store_write_all(host, &key_path, &value_bytes)
+}
+
+/// Returns every account known to the rollup, bounded by
+/// [MAX_PROOF_ACCOUNTS].
+pub fn list_accounts<Host: Runtime>(host: &mut Host) -> Result<Vec<(H160, Account)>, Error> {
+    let addresses: Vec<H160> = read_fixed_size_index::<_, _, _, ADDRESS_SIZE>(
+        host,
+        &ACCOUNTS_INDEX,
+        MAX_PROOF_ACCOUNTS,
+        Error::TooManyAccounts,
+        H160::from,
+    )?;
+
+    addresses
+        .into_iter()
+        .map(|address| {
+            let account = read_account(host, &account_path(&address)?)?;
+            Ok((address, account))
+        })
+        .collect()
+}
+
+/// Returns every storage slot ever touched for the account at
+/// `account_path`, bounded by [MAX_PROOF_STORAGE_SLOTS].
+pub fn list_storage<Host: Runtime>(
+    host: &mut Host,
+    account_path: &OwnedPath,
+) -> Result<Vec<(H256, U256)>, Error> {
+    let index_path = concat(account_path, &ACCOUNT_STORAGE_INDEX)?;
+    let keys: Vec<H256> = read_fixed_size_index::<_, _, _, WORD_SIZE>(
+        host,
+        &index_path,
+        MAX_PROOF_STORAGE_SLOTS,
+        Error::TooManyStorageSlots,
+        H256::from,
+    )?;
+
+    let storage_path = concat(account_path, &ACCOUNT_STORAGE)?;
+    keys.into_iter()
+        .map(|key| {
+            let raw_key_path: Vec<u8> = format!("/{}", hex::encode(key.as_bytes())).into();
+            let key_path = concat(&storage_path, &OwnedPath::try_from(raw_key_path)?)?;
+            let value = U256::from_little_endian(&store_read_all(host, &key_path)?);
+            Ok((key, value))
+        })
+        .collect()
+}
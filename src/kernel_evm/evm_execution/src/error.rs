@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use tezos_smart_rollup_host::path::PathError;
+use tezos_smart_rollup_host::runtime::RuntimeError;
+
+#[derive(Debug)]
+pub enum Error {
+    Runtime(RuntimeError),
+    Path(PathError),
+    /// `list_accounts` was asked to read back more addresses than
+    /// [crate::account_storage::MAX_PROOF_ACCOUNTS].
+    TooManyAccounts,
+    /// `list_storage` was asked to read back more storage slots than
+    /// [crate::account_storage::MAX_PROOF_STORAGE_SLOTS].
+    TooManyStorageSlots,
+    /// An index (`ACCOUNTS_INDEX`/`ACCOUNT_STORAGE_INDEX`) held a number of
+    /// bytes that isn't a whole multiple of its fixed entry size, i.e. its
+    /// last entry was truncated. Previously this silently dropped the last
+    /// entry instead of surfacing the corruption.
+    TruncatedIndex,
+}
+
+impl From<RuntimeError> for Error {
+    fn from(error: RuntimeError) -> Self {
+        Error::Runtime(error)
+    }
+}
+
+impl From<PathError> for Error {
+    fn from(error: PathError) -> Self {
+        Error::Path(error)
+    }
+}
@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use tezos_ethereum::transaction::TransactionHash;
+use tezos_smart_rollup_host::path::PathError;
+use tezos_smart_rollup_host::runtime::RuntimeError;
+
+#[derive(Debug)]
+pub enum StorageError {
+    /// The value read from storage doesn't have the expected size.
+    InvalidLoadValue { expected: usize, actual: usize },
+    /// `eth_getLogs` was asked to return more logs than [crate::storage::MAX_SUPPORTED_LOGS].
+    TooManyLogs,
+    /// `eth_getLogs` was asked to scan more blocks than
+    /// [crate::storage::MAX_SCANNED_BLOCKS].
+    TooManyBlocksRequested,
+    /// The hash of a reassembled chunked transaction doesn't match the hash
+    /// it was announced under.
+    InvalidChunkedTransactionHash {
+        expected: TransactionHash,
+        actual: TransactionHash,
+    },
+    /// A chunked transaction was created with a number of chunks that could
+    /// never be completed (i.e. `0`).
+    InvalidChunkedTransactionNumChunks(u16),
+    /// `eth_getProof` was asked to prove state at a block other than the
+    /// current one: the rollup only keeps the latest state in durable
+    /// storage, so no historical proof can be produced.
+    ProofUnavailableForBlock {
+        requested: primitive_types::U256,
+        current: primitive_types::U256,
+    },
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Runtime(RuntimeError),
+    Path(PathError),
+    Storage(StorageError),
+    Transaction(tezos_ethereum::transaction::TransactionError),
+    Execution(evm_execution::error::Error),
+}
+
+impl From<RuntimeError> for Error {
+    fn from(error: RuntimeError) -> Self {
+        Error::Runtime(error)
+    }
+}
+
+impl From<PathError> for Error {
+    fn from(error: PathError) -> Self {
+        Error::Path(error)
+    }
+}
+
+impl From<StorageError> for Error {
+    fn from(error: StorageError) -> Self {
+        Error::Storage(error)
+    }
+}
+
+impl From<tezos_ethereum::transaction::TransactionError> for Error {
+    fn from(error: tezos_ethereum::transaction::TransactionError) -> Self {
+        Error::Transaction(error)
+    }
+}
+
+impl From<evm_execution::error::Error> for Error {
+    fn from(error: evm_execution::error::Error) -> Self {
+        Error::Execution(error)
+    }
+}